@@ -0,0 +1,768 @@
+//! The `#[subenum(...)]` attribute macro itself. Split out from the
+//! `subenum` crate because a `proc-macro = true` crate can only export
+//! `#[proc_macro*]` functions -- the shared `SubenumConvertError` type and
+//! the public-facing docs live in `subenum` instead, which re-exports
+//! [`subenum`](macro@subenum) from here.
+
+use std::collections::{BTreeMap, HashMap, HashSet};
+
+use proc_macro::TokenStream;
+use proc_macro2::TokenStream as TokenStream2;
+use quote::{format_ident, quote};
+use syn::punctuated::Punctuated;
+use syn::token::Comma;
+use syn::parse::{Parse, ParseStream};
+use syn::{
+    parenthesized, parse_macro_input, parse_quote, Attribute, Expr, ExprLit, ExprUnary, Fields,
+    Generics, Ident, ItemEnum, Lit, UnOp, Variant, Visibility,
+};
+
+/// A single variant of the parent enum, together with the subenums it was
+/// tagged as belonging to.
+struct TaggedVariant {
+    variant: Variant,
+    subenums: Vec<Ident>,
+}
+
+/// One entry in `#[subenum(Dog(handler), Small)]`: a subenum name, plus
+/// whether it was suffixed with `(handler)` to opt into a generated
+/// `handle_dog!` dispatch macro. Folding the opt-in into this list (rather
+/// than a second, separate attribute) means there's no attribute ordering
+/// for users to get wrong.
+struct SubenumArg {
+    name: Ident,
+    handler: bool,
+}
+
+impl Parse for SubenumArg {
+    fn parse(input: ParseStream) -> syn::Result<Self> {
+        let name: Ident = input.parse()?;
+        if input.peek(syn::token::Paren) {
+            let content;
+            parenthesized!(content in input);
+            let marker: Ident = content.parse()?;
+            if marker != "handler" {
+                return Err(syn::Error::new(
+                    marker.span(),
+                    "expected `handler`, the only supported subenum modifier",
+                ));
+            }
+            Ok(SubenumArg { name, handler: true })
+        } else {
+            Ok(SubenumArg { name, handler: false })
+        }
+    }
+}
+
+/// `#[subenum(Dog, Small)]` applied to an enum. A subenum name can be
+/// suffixed with `(handler)`, e.g. `#[subenum(Dog(handler), Small)]`, to
+/// additionally generate a `handle_dog!` dispatch macro for it.
+#[proc_macro_attribute]
+pub fn subenum(args: TokenStream, input: TokenStream) -> TokenStream {
+    let subenum_args = parse_macro_input!(args with Punctuated::<SubenumArg, Comma>::parse_terminated);
+    let subenum_names: Vec<Ident> = subenum_args.iter().map(|arg| arg.name.clone()).collect();
+    let handler_names: Vec<Ident> = subenum_args
+        .iter()
+        .filter(|arg| arg.handler)
+        .map(|arg| arg.name.clone())
+        .collect();
+
+    let mut item_enum = parse_macro_input!(input as ItemEnum);
+    let parent_ident = item_enum.ident.clone();
+    let parent_vis = item_enum.vis.clone();
+    let generics = item_enum.generics.clone();
+
+    let parent_attrs: Vec<Attribute> = item_enum
+        .attrs
+        .iter()
+        .filter(|attr| !attr.path().is_ident("subenum"))
+        .cloned()
+        .collect();
+
+    // Pull the per-variant `#[subenum(...)]` memberships out, stripping the
+    // attribute from the variant we re-emit on the parent.
+    let tagged: Vec<TaggedVariant> = item_enum
+        .variants
+        .iter()
+        .map(|variant| {
+            let subenums = variant_memberships(variant);
+            let mut variant = variant.clone();
+            variant.attrs.retain(|attr| !attr.path().is_ident("subenum"));
+            TaggedVariant { variant, subenums }
+        })
+        .collect();
+
+    item_enum.attrs = parent_attrs.clone();
+    for variant in item_enum.variants.iter_mut() {
+        variant.attrs.retain(|attr| !attr.path().is_ident("subenum"));
+    }
+
+    let discriminants = compute_discriminants(&tagged);
+    let discriminant_by_variant: HashMap<String, i64> = tagged
+        .iter()
+        .zip(discriminants.iter().copied())
+        .map(|(tagged, value)| (tagged.variant.ident.to_string(), value))
+        .collect();
+
+    let mut output = quote! { #item_enum };
+
+    let parent_with_discriminants: Vec<(&TaggedVariant, i64)> =
+        tagged.iter().zip(discriminants.iter().copied()).collect();
+    output.extend(generate_discriminant_conversions(
+        &parent_ident,
+        &generics,
+        &parent_with_discriminants,
+    ));
+    let parent_variants: Vec<&Variant> = tagged.iter().map(|tagged| &tagged.variant).collect();
+    output.extend(generate_payload_from_impls(
+        &parent_ident,
+        &generics,
+        &parent_variants,
+    ));
+
+    let mut subenum_members: Vec<(&Ident, Vec<&TaggedVariant>)> = Vec::new();
+
+    for name in &subenum_names {
+        let members: Vec<&TaggedVariant> = tagged
+            .iter()
+            .filter(|tagged| tagged.subenums.iter().any(|s| s == name))
+            .collect();
+        let members_with_discriminants: Vec<(&TaggedVariant, i64)> = members
+            .iter()
+            .map(|tagged| {
+                let value = discriminant_by_variant[&tagged.variant.ident.to_string()];
+                (*tagged, value)
+            })
+            .collect();
+
+        output.extend(generate_subenum(
+            name,
+            &parent_vis,
+            &parent_attrs,
+            &generics,
+            &members,
+        ));
+        output.extend(generate_conversions(name, &parent_ident, &generics, &members));
+        output.extend(generate_partial_eq(name, &parent_ident, &generics, &members));
+        output.extend(generate_variants_fn(name, &generics, &members));
+        output.extend(generate_discriminant_conversions(
+            name,
+            &generics,
+            &members_with_discriminants,
+        ));
+        if handler_names.contains(name) {
+            output.extend(generate_handler_macro(name, &members, &parent_vis));
+        }
+        let member_variants: Vec<&Variant> =
+            members.iter().map(|tagged| &tagged.variant).collect();
+        output.extend(generate_payload_from_impls(name, &generics, &member_variants));
+
+        subenum_members.push((name, members));
+    }
+
+    // Direct conversions between overlapping subenums (e.g. `Westie`
+    // belongs to both `Dog` and `Small`), so users don't have to round-trip
+    // through the parent enum.
+    for (from_name, from_members) in &subenum_members {
+        for (to_name, to_members) in &subenum_members {
+            if from_name == to_name {
+                continue;
+            }
+            output.extend(generate_sibling_conversion(
+                from_name,
+                from_members,
+                to_name,
+                to_members,
+                &generics,
+            ));
+        }
+    }
+
+    output.into()
+}
+
+/// Resolves each variant's discriminant, honoring an explicit `= N` and
+/// otherwise continuing the running count from the previous variant (as
+/// the compiler does). Only literal integer discriminants are understood;
+/// anything else falls back to the running count, same as if no
+/// discriminant had been given.
+fn compute_discriminants(tagged: &[TaggedVariant]) -> Vec<i64> {
+    let mut next: i64 = 0;
+    tagged
+        .iter()
+        .map(|tagged| {
+            let value = tagged
+                .variant
+                .discriminant
+                .as_ref()
+                .and_then(|(_, expr)| literal_i64(expr))
+                .unwrap_or(next);
+            next = value + 1;
+            value
+        })
+        .collect()
+}
+
+fn literal_i64(expr: &Expr) -> Option<i64> {
+    match expr {
+        Expr::Lit(ExprLit {
+            lit: Lit::Int(lit), ..
+        }) => lit.base10_parse::<i64>().ok(),
+        // `= -5` parses as a unary negation of a literal, not a literal
+        // itself, so it needs to be unwrapped before `base10_parse` ever
+        // sees it.
+        Expr::Unary(ExprUnary {
+            op: UnOp::Neg(_),
+            expr,
+            ..
+        }) => literal_i64(expr).map(|value| -value),
+        _ => None,
+    }
+}
+
+/// Reads the `#[subenum(A, B)]` attribute on a variant, returning the list
+/// of subenum names it belongs to (empty if it has no such attribute).
+fn variant_memberships(variant: &Variant) -> Vec<Ident> {
+    variant
+        .attrs
+        .iter()
+        .filter(|attr| attr.path().is_ident("subenum"))
+        .flat_map(|attr| {
+            attr.parse_args_with(Punctuated::<Ident, Comma>::parse_terminated)
+                .unwrap_or_default()
+        })
+        .collect()
+}
+
+/// Emits the subenum's own `enum Dog { ... }` definition, carrying forward
+/// the parent's visibility, non-`subenum` attributes (so things like
+/// `#[derive(Debug, Clone)]` apply equally to the subenum), and generic
+/// parameters -- a subenum variant can hold a field of the parent's generic
+/// type, so it needs to declare that same parameter itself.
+fn generate_subenum(
+    name: &Ident,
+    vis: &Visibility,
+    attrs: &[Attribute],
+    generics: &Generics,
+    members: &[&TaggedVariant],
+) -> TokenStream2 {
+    let variants = members.iter().map(|tagged| &tagged.variant);
+    let (impl_generics, _, where_clause) = generics.split_for_impl();
+    quote! {
+        #(#attrs)*
+        #vis enum #name #impl_generics #where_clause {
+            #(#variants),*
+        }
+    }
+}
+
+/// Builds a pattern that destructures a variant's fields, and a matching
+/// constructor expression that rebuilds the same variant from those
+/// bindings. Used to move field values across parent/subenum conversions
+/// without caring whether the variant is unit, tuple, or struct-like.
+fn pattern_and_constructor(enum_ident: &Ident, variant: &Variant) -> (TokenStream2, TokenStream2) {
+    let variant_ident = &variant.ident;
+    match &variant.fields {
+        Fields::Unit => (
+            quote! { #enum_ident::#variant_ident },
+            quote! { #enum_ident::#variant_ident },
+        ),
+        Fields::Unnamed(fields) => {
+            let bindings: Vec<Ident> = (0..fields.unnamed.len())
+                .map(|i| format_ident!("a{}", i))
+                .collect();
+            (
+                quote! { #enum_ident::#variant_ident(#(#bindings),*) },
+                quote! { #enum_ident::#variant_ident(#(#bindings),*) },
+            )
+        }
+        Fields::Named(fields) => {
+            let names: Vec<&Ident> = fields
+                .named
+                .iter()
+                .map(|f| f.ident.as_ref().expect("named field"))
+                .collect();
+            (
+                quote! { #enum_ident::#variant_ident { #(#names),* } },
+                quote! { #enum_ident::#variant_ident { #(#names),* } },
+            )
+        }
+    }
+}
+
+/// Generates `TryFrom<Parent> for Sub` and the infallible `From<Sub> for
+/// Parent` that goes the other way.
+fn generate_conversions(
+    name: &Ident,
+    parent_ident: &Ident,
+    generics: &Generics,
+    members: &[&TaggedVariant],
+) -> TokenStream2 {
+    let parent_name = parent_ident.to_string();
+    let sub_name = name.to_string();
+    let (impl_generics, ty_generics, where_clause) = generics.split_for_impl();
+
+    let try_from_arms = members.iter().map(|tagged| {
+        let (parent_pat, _) = pattern_and_constructor(parent_ident, &tagged.variant);
+        let (_, sub_ctor) = pattern_and_constructor(name, &tagged.variant);
+        quote! { #parent_pat => Ok(#sub_ctor) }
+    });
+
+    let from_arms = members.iter().map(|tagged| {
+        let (sub_pat, _) = pattern_and_constructor(name, &tagged.variant);
+        let (_, parent_ctor) = pattern_and_constructor(parent_ident, &tagged.variant);
+        quote! { #sub_pat => #parent_ctor }
+    });
+
+    quote! {
+        impl #impl_generics ::core::convert::TryFrom<#parent_ident #ty_generics> for #name #ty_generics #where_clause {
+            type Error = ::subenum::SubenumConvertError;
+
+            fn try_from(value: #parent_ident #ty_generics) -> ::core::result::Result<Self, Self::Error> {
+                match value {
+                    #(#try_from_arms,)*
+                    _ => Err(::subenum::SubenumConvertError {
+                        from: #parent_name,
+                        to: #sub_name,
+                    }),
+                }
+            }
+        }
+
+        impl #impl_generics ::core::convert::From<#name #ty_generics> for #parent_ident #ty_generics #where_clause {
+            fn from(value: #name #ty_generics) -> Self {
+                match value {
+                    #(#from_arms,)*
+                }
+            }
+        }
+    }
+}
+
+/// Like [`pattern_and_constructor`], but builds only a destructuring
+/// pattern, with every binding suffixed to keep it distinct from whatever
+/// the other side of a two-pattern match arm binds. Returns the pattern and
+/// the bindings in field order, so callers can compare them pairwise.
+fn bound_pattern(enum_ident: &Ident, variant: &Variant, suffix: &str) -> (TokenStream2, Vec<Ident>) {
+    let variant_ident = &variant.ident;
+    match &variant.fields {
+        Fields::Unit => (quote! { #enum_ident::#variant_ident }, Vec::new()),
+        Fields::Unnamed(fields) => {
+            let bindings: Vec<Ident> = (0..fields.unnamed.len())
+                .map(|i| format_ident!("a{}{}", i, suffix))
+                .collect();
+            (
+                quote! { #enum_ident::#variant_ident(#(#bindings),*) },
+                bindings,
+            )
+        }
+        Fields::Named(fields) => {
+            let names: Vec<&Ident> = fields
+                .named
+                .iter()
+                .map(|f| f.ident.as_ref().expect("named field"))
+                .collect();
+            let bindings: Vec<Ident> = names
+                .iter()
+                .map(|name| format_ident!("{}{}", name, suffix))
+                .collect();
+            let pat_fields = names
+                .iter()
+                .zip(bindings.iter())
+                .map(|(field, binding)| quote! { #field: #binding });
+            (
+                quote! { #enum_ident::#variant_ident { #(#pat_fields),* } },
+                bindings,
+            )
+        }
+    }
+}
+
+/// `lhs == rhs` across paired-up bindings from two [`bound_pattern`] calls
+/// on the same variant, `&&`-ed together (or `true` for a unit variant).
+fn fields_equal(lhs: &[Ident], rhs: &[Ident]) -> TokenStream2 {
+    if lhs.is_empty() {
+        return quote! { true };
+    }
+    let checks = lhs.iter().zip(rhs.iter()).map(|(l, r)| quote! { #l == #r });
+    quote! { #(#checks)&&* }
+}
+
+/// Adds `bound` to every type parameter in `generics`, the same way
+/// `#[derive(...)]` adds bounds for its own impls. Needed wherever a
+/// generated impl's body applies a trait to a field of a generic type
+/// parameter -- the parent's own generics don't necessarily carry that
+/// bound themselves.
+fn add_trait_bound(generics: &Generics, bound: syn::Path) -> Generics {
+    let mut generics = generics.clone();
+    for param in generics.type_params_mut() {
+        param.bounds.push(syn::TypeParamBound::Trait(syn::TraitBound {
+            paren_token: None,
+            modifier: syn::TraitBoundModifier::None,
+            lifetimes: None,
+            path: bound.clone(),
+        }));
+    }
+    generics
+}
+
+/// Generates `PartialEq` in both directions between the parent and a
+/// subenum, comparing field-by-field rather than requiring either side to
+/// already implement `PartialEq` against the other. The comparison does
+/// need each of the enum's own generic type parameters to implement
+/// `PartialEq`, since a variant's field might be of that type -- the
+/// parent's own generics don't guarantee that bound, so it's added here,
+/// the same way `#[derive(PartialEq)]` would add it for its own impl.
+fn generate_partial_eq(
+    name: &Ident,
+    parent_ident: &Ident,
+    generics: &Generics,
+    members: &[&TaggedVariant],
+) -> TokenStream2 {
+    let bounded_generics = add_trait_bound(generics, parse_quote!(::core::cmp::PartialEq));
+    let (impl_generics, ty_generics, where_clause) = bounded_generics.split_for_impl();
+
+    let sub_eq_parent_arms = members.iter().map(|tagged| {
+        let (sub_pat, sub_bindings) = bound_pattern(name, &tagged.variant, "_lhs");
+        let (parent_pat, parent_bindings) = bound_pattern(parent_ident, &tagged.variant, "_rhs");
+        let comparison = fields_equal(&sub_bindings, &parent_bindings);
+        quote! { (#sub_pat, #parent_pat) => #comparison }
+    });
+
+    let parent_eq_sub_arms = members.iter().map(|tagged| {
+        let (parent_pat, parent_bindings) = bound_pattern(parent_ident, &tagged.variant, "_lhs");
+        let (sub_pat, sub_bindings) = bound_pattern(name, &tagged.variant, "_rhs");
+        let comparison = fields_equal(&parent_bindings, &sub_bindings);
+        quote! { (#parent_pat, #sub_pat) => #comparison }
+    });
+
+    quote! {
+        impl #impl_generics ::core::cmp::PartialEq<#parent_ident #ty_generics> for #name #ty_generics #where_clause {
+            fn eq(&self, other: &#parent_ident #ty_generics) -> bool {
+                match (self, other) {
+                    #(#sub_eq_parent_arms,)*
+                    _ => false,
+                }
+            }
+        }
+
+        impl #impl_generics ::core::cmp::PartialEq<#name #ty_generics> for #parent_ident #ty_generics #where_clause {
+            fn eq(&self, other: &#name #ty_generics) -> bool {
+                match (self, other) {
+                    #(#parent_eq_sub_arms,)*
+                    _ => false,
+                }
+            }
+        }
+    }
+}
+
+/// Generates `to_discriminant()` and `TryFrom<i64>` for an enum whose
+/// variants are all fieldless, using the resolved discriminant paired with
+/// each member. Skipped entirely (not an error) when any member carries
+/// fields, matching the `#[repr]`-style restriction those fieldless-only
+/// conversions already have in the language itself.
+fn generate_discriminant_conversions(
+    name: &Ident,
+    generics: &Generics,
+    members: &[(&TaggedVariant, i64)],
+) -> TokenStream2 {
+    let all_unit = members
+        .iter()
+        .all(|(tagged, _)| matches!(tagged.variant.fields, Fields::Unit));
+    if !all_unit {
+        return TokenStream2::new();
+    }
+
+    let enum_name = name.to_string();
+    let (impl_generics, ty_generics, where_clause) = generics.split_for_impl();
+
+    let try_from_i64_arms = members.iter().map(|(tagged, value)| {
+        let ident = &tagged.variant.ident;
+        quote! { #value => Ok(#name::#ident) }
+    });
+
+    // u64 can't represent negative discriminants, so those members simply
+    // aren't reachable through `TryFrom<u64>`.
+    let try_from_u64_arms = members.iter().filter_map(|(tagged, value)| {
+        let ident = &tagged.variant.ident;
+        u64::try_from(*value)
+            .ok()
+            .map(|value| quote! { #value => Ok(#name::#ident) })
+    });
+
+    // An empty subenum has no inhabitants, so there's no `self` a caller
+    // could ever pass to `to_discriminant`. Skip it entirely rather than
+    // matching `&self` with zero arms, which rustc rejects (E0004):
+    // references are never considered uninhabited, even of an uninhabited
+    // type.
+    let to_discriminant_impl = if members.is_empty() {
+        TokenStream2::new()
+    } else {
+        let to_discriminant_arms = members.iter().map(|(tagged, value)| {
+            let ident = &tagged.variant.ident;
+            quote! { #name::#ident => #value }
+        });
+        quote! {
+            impl #impl_generics #name #ty_generics #where_clause {
+                /// This variant's discriminant value.
+                pub const fn to_discriminant(&self) -> i64 {
+                    match self {
+                        #(#to_discriminant_arms,)*
+                    }
+                }
+            }
+        }
+    };
+
+    quote! {
+        #to_discriminant_impl
+
+        impl #impl_generics ::core::convert::TryFrom<i64> for #name #ty_generics #where_clause {
+            type Error = ::subenum::SubenumConvertError;
+
+            fn try_from(value: i64) -> ::core::result::Result<Self, Self::Error> {
+                match value {
+                    #(#try_from_i64_arms,)*
+                    _ => Err(::subenum::SubenumConvertError {
+                        from: "i64",
+                        to: #enum_name,
+                    }),
+                }
+            }
+        }
+
+        impl #impl_generics ::core::convert::TryFrom<u64> for #name #ty_generics #where_clause {
+            type Error = ::subenum::SubenumConvertError;
+
+            fn try_from(value: u64) -> ::core::result::Result<Self, Self::Error> {
+                match value {
+                    #(#try_from_u64_arms,)*
+                    _ => Err(::subenum::SubenumConvertError {
+                        from: "u64",
+                        to: #enum_name,
+                    }),
+                }
+            }
+        }
+    }
+}
+
+/// Generates a direct conversion from one subenum to another, skipping the
+/// parent enum entirely. Infallible `From` when every variant of `from` is
+/// also a member of `to`; otherwise a `TryFrom` that succeeds only for the
+/// shared variants. Returns no tokens when the two subenums don't overlap
+/// at all.
+fn generate_sibling_conversion(
+    from_name: &Ident,
+    from_members: &[&TaggedVariant],
+    to_name: &Ident,
+    to_members: &[&TaggedVariant],
+    generics: &Generics,
+) -> TokenStream2 {
+    let to_idents: HashSet<String> = to_members
+        .iter()
+        .map(|tagged| tagged.variant.ident.to_string())
+        .collect();
+    let shared: Vec<&&TaggedVariant> = from_members
+        .iter()
+        .filter(|tagged| to_idents.contains(&tagged.variant.ident.to_string()))
+        .collect();
+
+    if shared.is_empty() {
+        return TokenStream2::new();
+    }
+
+    let (impl_generics, ty_generics, where_clause) = generics.split_for_impl();
+
+    if shared.len() == from_members.len() {
+        let arms = from_members.iter().map(|tagged| {
+            let (from_pat, _) = pattern_and_constructor(from_name, &tagged.variant);
+            let (_, to_ctor) = pattern_and_constructor(to_name, &tagged.variant);
+            quote! { #from_pat => #to_ctor }
+        });
+        quote! {
+            impl #impl_generics ::core::convert::From<#from_name #ty_generics> for #to_name #ty_generics #where_clause {
+                fn from(value: #from_name #ty_generics) -> Self {
+                    match value {
+                        #(#arms,)*
+                    }
+                }
+            }
+        }
+    } else {
+        let from_name_str = from_name.to_string();
+        let to_name_str = to_name.to_string();
+        let arms = shared.iter().map(|tagged| {
+            let (from_pat, _) = pattern_and_constructor(from_name, &tagged.variant);
+            let (_, to_ctor) = pattern_and_constructor(to_name, &tagged.variant);
+            quote! { #from_pat => Ok(#to_ctor) }
+        });
+        quote! {
+            impl #impl_generics ::core::convert::TryFrom<#from_name #ty_generics> for #to_name #ty_generics #where_clause {
+                type Error = ::subenum::SubenumConvertError;
+
+                fn try_from(value: #from_name #ty_generics) -> ::core::result::Result<Self, Self::Error> {
+                    match value {
+                        #(#arms,)*
+                        _ => Err(::subenum::SubenumConvertError {
+                            from: #from_name_str,
+                            to: #to_name_str,
+                        }),
+                    }
+                }
+            }
+        }
+    }
+}
+
+/// Generates `impl From<T> for Enum` for every single-field tuple variant
+/// whose field type `T` appears on exactly one of `variants` -- i.e. `T` is
+/// unambiguous within this enum. Variants wrapping a type shared with
+/// another variant are skipped, since a single `From` impl couldn't know
+/// which one to build. The parent enum's generics and where-clause are
+/// threaded onto the generated impl so this works for generic payloads too.
+fn generate_payload_from_impls(
+    enum_name: &Ident,
+    generics: &Generics,
+    variants: &[&Variant],
+) -> TokenStream2 {
+    let mut by_type: BTreeMap<String, Vec<&Variant>> = BTreeMap::new();
+    for variant in variants {
+        if let Fields::Unnamed(fields) = &variant.fields {
+            if fields.unnamed.len() == 1 {
+                let ty = &fields.unnamed.first().unwrap().ty;
+                by_type
+                    .entry(quote! { #ty }.to_string())
+                    .or_default()
+                    .push(*variant);
+            }
+        }
+    }
+
+    let (impl_generics, ty_generics, where_clause) = generics.split_for_impl();
+
+    let mut output = TokenStream2::new();
+    for variants in by_type.values() {
+        let [variant] = variants.as_slice() else {
+            continue;
+        };
+        let variant_ident = &variant.ident;
+        let ty = match &variant.fields {
+            Fields::Unnamed(fields) => &fields.unnamed.first().unwrap().ty,
+            _ => unreachable!("filtered to single-field tuple variants above"),
+        };
+
+        output.extend(quote! {
+            impl #impl_generics ::core::convert::From<#ty> for #enum_name #ty_generics #where_clause {
+                fn from(value: #ty) -> Self {
+                    #enum_name::#variant_ident(value)
+                }
+            }
+        });
+    }
+    output
+}
+
+/// Generates `Dog::variants()` / `Dog::count()` for subenums whose members
+/// are all fieldless. Skipped (not an error) when any member carries
+/// fields, since there's no value to put in the array.
+fn generate_variants_fn(name: &Ident, generics: &Generics, members: &[&TaggedVariant]) -> TokenStream2 {
+    let all_unit = members
+        .iter()
+        .all(|tagged| matches!(tagged.variant.fields, Fields::Unit));
+    if !all_unit {
+        return TokenStream2::new();
+    }
+
+    let count = members.len();
+    let idents = members.iter().map(|tagged| &tagged.variant.ident);
+    let (impl_generics, ty_generics, where_clause) = generics.split_for_impl();
+
+    quote! {
+        impl #impl_generics #name #ty_generics #where_clause {
+            /// Every variant of this subenum, in declaration order.
+            pub const fn variants() -> [#name #ty_generics; #count] {
+                [#(#name::#idents),*]
+            }
+
+            /// The number of variants in this subenum.
+            pub const fn count() -> usize {
+                #count
+            }
+        }
+    }
+}
+
+/// Generates a `handle_dog!($value, $handler)` declarative macro that
+/// expands to a full `match` over every variant of `name`, dispatching
+/// each one to a snake_cased method on `$handler`. Exported crate-wide
+/// only when the parent enum (and so, transitively, the subenum) is
+/// `pub`, since an unexported `macro_rules!` isn't visible outside its
+/// defining module anyway.
+fn generate_handler_macro(name: &Ident, members: &[&TaggedVariant], vis: &Visibility) -> TokenStream2 {
+    let macro_name = format_ident!("handle_{}", to_snake_case(&name.to_string()));
+    let arms = members
+        .iter()
+        .map(|tagged| handler_arm(name, &tagged.variant));
+    let export = matches!(vis, Visibility::Public(_)).then(|| quote! { #[macro_export] });
+
+    quote! {
+        #export
+        macro_rules! #macro_name {
+            ($value:expr, $handler:expr) => {
+                match $value {
+                    #(#arms,)*
+                }
+            };
+        }
+    }
+}
+
+/// One arm of a handler macro: `Dog::Boxer => $handler.handle_boxer(),`
+/// for a unit variant, binding fields positionally as `a0, a1, ...` (or by
+/// name, for struct variants) and forwarding them to the method.
+fn handler_arm(enum_ident: &Ident, variant: &Variant) -> TokenStream2 {
+    let variant_ident = &variant.ident;
+    let method = format_ident!("handle_{}", to_snake_case(&variant_ident.to_string()));
+    match &variant.fields {
+        Fields::Unit => quote! {
+            #enum_ident::#variant_ident => $handler.#method()
+        },
+        Fields::Unnamed(fields) => {
+            let bindings: Vec<Ident> = (0..fields.unnamed.len())
+                .map(|i| format_ident!("a{}", i))
+                .collect();
+            quote! {
+                #enum_ident::#variant_ident(#(#bindings),*) => $handler.#method(#(#bindings),*)
+            }
+        }
+        Fields::Named(fields) => {
+            let names: Vec<&Ident> = fields
+                .named
+                .iter()
+                .map(|f| f.ident.as_ref().expect("named field"))
+                .collect();
+            quote! {
+                #enum_ident::#variant_ident { #(#names),* } => $handler.#method(#(#names),*)
+            }
+        }
+    }
+}
+
+/// A minimal CamelCase/PascalCase-to-snake_case conversion, good enough
+/// for turning variant and subenum names into method/macro names.
+fn to_snake_case(ident: &str) -> String {
+    let mut out = String::new();
+    for (i, ch) in ident.chars().enumerate() {
+        if ch.is_uppercase() {
+            if i != 0 {
+                out.push('_');
+            }
+            out.extend(ch.to_lowercase());
+        } else {
+            out.push(ch);
+        }
+    }
+    out
+}
@@ -0,0 +1,49 @@
+//! Your first proc macro: `#[subenum(...)]` carves named subsets out of an
+//! enum.
+//!
+//! Given a "parent" enum, tag individual variants with the subenums they
+//! belong to:
+//!
+//! ```ignore
+//! #[subenum(Dog, Small)]
+//! enum Canis {
+//!     Wolf,
+//!     #[subenum(Dog)]
+//!     Boxer,
+//!     #[subenum(Dog, Small)]
+//!     Westie,
+//! }
+//! ```
+//!
+//! and the macro generates a `Dog` enum and a `Small` enum containing just
+//! those variants, plus `TryFrom`/`From` impls for moving values between the
+//! parent and each subenum.
+//!
+//! The attribute itself lives in the companion `subenum-macros` crate, since
+//! a `proc-macro = true` crate can only export `#[proc_macro*]` functions;
+//! everything else (this doc comment, the shared error type) lives here.
+
+pub use subenum_macros::subenum;
+
+/// The error returned when a conversion finds no matching variant: the
+/// value's variant is not a member of the subenum being converted into, or
+/// an integer matches none of the target enum's discriminants.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct SubenumConvertError {
+    /// The name of the type being converted from.
+    pub from: &'static str,
+    /// The name of the type that could not be constructed.
+    pub to: &'static str,
+}
+
+impl ::core::fmt::Display for SubenumConvertError {
+    fn fmt(&self, f: &mut ::core::fmt::Formatter<'_>) -> ::core::fmt::Result {
+        write!(
+            f,
+            "cannot convert `{}` into `{}`: no matching variant in the target enum",
+            self.from, self.to
+        )
+    }
+}
+
+impl std::error::Error for SubenumConvertError {}
@@ -1,8 +1,8 @@
 #![allow(dead_code)]
 use subenum::subenum;
 
-#[subenum(Dog, Small)]
-#[derive(Debug, Copy, Clone)]
+#[subenum(Dog(handler), Small)]
+#[derive(Debug, Copy, Clone, PartialEq)]
 enum Canis {
     Wolf,
     #[subenum(Dog)]
@@ -24,3 +24,133 @@ fn test_dog() {
 
     assert_eq!(dog, canis2);
 }
+
+#[test]
+fn test_variants_and_count() {
+    assert_eq!(Dog::count(), 4);
+    assert_eq!(
+        Dog::variants(),
+        [Dog::GermanShephard, Dog::Boxer, Dog::GolderRetriever, Dog::Westie]
+    );
+    assert_eq!(Small::count(), 1);
+    assert_eq!(Small::variants(), [Small::Westie]);
+}
+
+#[subenum(Neg)]
+#[derive(Debug, Copy, Clone, PartialEq)]
+enum Temperature {
+    #[subenum(Neg)]
+    BelowZero = -5,
+    Zero,
+}
+
+#[test]
+fn test_discriminants_with_negative_value() {
+    assert_eq!(Temperature::BelowZero.to_discriminant(), -5);
+    assert_eq!(Temperature::Zero.to_discriminant(), -4);
+
+    assert_eq!(Temperature::try_from(-5i64), Ok(Temperature::BelowZero));
+    assert_eq!(Temperature::try_from(-4i64), Ok(Temperature::Zero));
+    assert!(Temperature::try_from(0i64).is_err());
+
+    // Every variant's discriminant is negative, so none of them is
+    // reachable through `TryFrom<u64>`.
+    assert!(Temperature::try_from(5u64).is_err());
+
+    assert_eq!(Neg::try_from(-5i64), Ok(Neg::BelowZero));
+    assert!(Neg::try_from(5u64).is_err());
+}
+
+struct Printer;
+
+impl Printer {
+    fn handle_german_shephard(&self) -> &'static str {
+        "german shephard"
+    }
+
+    fn handle_boxer(&self) -> &'static str {
+        "boxer"
+    }
+
+    fn handle_golder_retriever(&self) -> &'static str {
+        "golder retriever"
+    }
+
+    fn handle_westie(&self) -> &'static str {
+        "westie"
+    }
+}
+
+#[test]
+fn test_handler_macro() {
+    let printer = Printer;
+    let dog = Dog::Boxer;
+    assert_eq!(handle_dog!(dog, printer), "boxer");
+
+    let dog = Dog::Westie;
+    assert_eq!(handle_dog!(dog, printer), "westie");
+}
+
+#[test]
+fn test_sibling_conversions() {
+    // Every `Small` variant is also a `Dog` variant, so that direction is
+    // infallible.
+    let dog: Dog = Small::Westie.into();
+    assert_eq!(dog, Dog::Westie);
+
+    // Not every `Dog` variant is a `Small` variant, so that direction can
+    // fail.
+    let small = Small::try_from(Dog::Westie).unwrap();
+    assert_eq!(small, Small::Westie);
+    assert!(Small::try_from(Dog::Boxer).is_err());
+}
+
+#[subenum(Vocal)]
+#[derive(Debug, Clone, PartialEq)]
+enum Sound {
+    #[subenum(Vocal)]
+    Meow(i32),
+    Woof,
+}
+
+#[test]
+fn test_payload_from_impl() {
+    let sound: Sound = 3.into();
+    assert_eq!(sound, Sound::Meow(3));
+
+    let vocal: Vocal = 7.into();
+    assert_eq!(vocal, Vocal::Meow(7));
+}
+
+#[subenum(Empty)]
+#[derive(Debug, Copy, Clone, PartialEq)]
+enum Canis2 {
+    Wolf,
+    Coyote,
+}
+
+#[test]
+fn test_empty_subenum() {
+    assert_eq!(Empty::count(), 0);
+    assert_eq!(Empty::variants(), [] as [Empty; 0]);
+    assert!(Empty::try_from(0i64).is_err());
+    assert!(Empty::try_from(0u64).is_err());
+}
+
+#[subenum(Sub)]
+#[derive(Debug, Clone, PartialEq)]
+enum Holder<T> {
+    #[subenum(Sub)]
+    Item(T),
+    Empty,
+}
+
+#[test]
+fn test_generic_payload() {
+    let holder = Holder::Item(5);
+    let sub = Sub::try_from(holder.clone()).unwrap();
+    assert_eq!(sub, holder);
+
+    let holder2: Holder<i32> = sub.into();
+    assert_eq!(holder2, holder);
+}